@@ -1,9 +1,13 @@
 use crate::error::{Error, Result};
+use crate::file_type::FileType;
+use crate::git::GitCell;
+use chrono::{DateTime, Local};
 use colored::Colorize;
 use std::{
     cmp, ffi, fmt, fs, io,
     os::unix::fs::{MetadataExt, PermissionsExt},
     path,
+    time::{Duration, SystemTime},
 };
 use terminal_size::{terminal_size, Width};
 
@@ -13,9 +17,64 @@ macro_rules! max_field_width {
     };
 }
 
-const KILOBYTE: u64 = 1000;
-const GIGABYTE: u64 = KILOBYTE * 1000;
-const TERABYTE: u64 = GIGABYTE * 1000;
+const SIX_MONTHS: Duration = Duration::from_secs(60 * 60 * 24 * 30 * 6);
+
+const SI_UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+const BINARY_UNITS: [&str; 6] = ["B", "Ki", "Mi", "Gi", "Ti", "Pi"];
+
+/// Which unit table (and base) `format_size` scales a byte count through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    /// SI units (base 1000), the default: `K`, `M`, `G`, ...
+    Si,
+    /// IEC units (base 1024), enabled with `--binary`/`-b`: `Ki`, `Mi`, `Gi`, ...
+    Binary,
+    /// Raw byte counts, enabled with `--bytes`.
+    Bytes,
+}
+
+impl Default for SizeUnit {
+    fn default() -> Self {
+        Self::Si
+    }
+}
+
+/// Scales `bytes` down through `unit`'s table until it fits under the base,
+/// printing one decimal place. Unlike the old `KILOBYTE`/`GIGABYTE` jump
+/// table, this walks every tier in between so a 5 MB file prints `5.0M`
+/// instead of silently skipping to `0.0G`.
+fn format_size(bytes: u64, unit: SizeUnit) -> String {
+    if unit == SizeUnit::Bytes {
+        return format!("{bytes}B");
+    }
+
+    let (base, units) = match unit {
+        SizeUnit::Si => (1000_f64, SI_UNITS),
+        SizeUnit::Binary => (1024_f64, BINARY_UNITS),
+        SizeUnit::Bytes => unreachable!(),
+    };
+
+    let mut value = bytes as f64;
+    let mut index = 0;
+
+    while value >= base && index < units.len() - 1 {
+        value /= base;
+        index += 1;
+    }
+
+    // rounding to one decimal can push the displayed value up to the next
+    // tier's threshold (e.g. 999.95 -> "1000.0K"); bump once more if so
+    if index < units.len() - 1 && (value * 10.0).round() / 10.0 >= base {
+        value /= base;
+        index += 1;
+    }
+
+    if index == 0 {
+        format!("{bytes}{}", units[0])
+    } else {
+        format!("{value:.1}{}", units[index])
+    }
+}
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
 pub enum Kind {
@@ -42,6 +101,7 @@ pub struct Entry {
     name: String,
     metadata: fs::Metadata,
     dir_entry: fs::DirEntry,
+    icons: bool,
 }
 
 impl PartialEq for Entry {
@@ -72,6 +132,74 @@ impl Ord for Entry {
     }
 }
 
+/// The key entries are ordered by at runtime. `Entry`'s own [`Ord`] impl
+/// stays fixed at `Name` (directories first, case-insensitive), but callers
+/// that want a different key build a comparator with [`comparator`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Name,
+    Size,
+    Time,
+    Extension,
+    Kind,
+}
+
+impl Default for SortField {
+    fn default() -> Self {
+        Self::Name
+    }
+}
+
+fn extension(entry: &Entry) -> String {
+    entry
+        .name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default()
+}
+
+fn sort_key_cmp(a: &Entry, b: &Entry, field: SortField) -> cmp::Ordering {
+    match field {
+        SortField::Name => a
+            .name
+            .to_lowercase()
+            .cmp(&b.name.to_lowercase())
+            .then_with(|| a.name.cmp(&b.name)),
+        // largest/newest first by default, matching `ls -S`/`-t`; `-r` flips it back
+        SortField::Size => b.metadata.size().cmp(&a.metadata.size()),
+        SortField::Time => b.metadata.mtime().cmp(&a.metadata.mtime()),
+        SortField::Extension => extension(a).cmp(&extension(b)),
+        SortField::Kind => a.kind.cmp(&b.kind),
+    }
+}
+
+/// Builds a comparator for `Vec<Entry>::sort_by` out of a [`SortField`], a
+/// reverse toggle and a directories-first toggle, so runtime sorting stays
+/// pluggable while `Entry`'s own `Ord` impl remains the canonical default.
+pub fn comparator(
+    field: SortField,
+    reverse: bool,
+    dirs_first: bool,
+) -> impl Fn(&Entry, &Entry) -> cmp::Ordering {
+    move |a, b| {
+        let ordering = if dirs_first {
+            match (a.kind == Kind::Directory, b.kind == Kind::Directory) {
+                (true, false) => cmp::Ordering::Less,
+                (false, true) => cmp::Ordering::Greater,
+                _ => sort_key_cmp(a, b, field),
+            }
+        } else {
+            sort_key_cmp(a, b, field)
+        };
+
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
 impl TryFrom<fs::DirEntry> for Entry {
     type Error = Error;
 
@@ -81,17 +209,20 @@ impl TryFrom<fs::DirEntry> for Entry {
             kind: dir_entry.file_type()?.into(),
             metadata: dir_entry.metadata()?,
             dir_entry,
+            icons: false,
         })
     }
 }
 
 impl fmt::Display for Entry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.icon_prefix())?;
+
         match self.kind {
             Kind::Directory => write!(f, "{}/", self.name.blue().bold()),
             Kind::Symlink => write!(f, "{}", self.name.bright_blue().underline()),
             Kind::File if self.is_executable() => write!(f, "{}", self.name.bright_green()),
-            Kind::File => write!(f, "{}", self.name),
+            Kind::File => write!(f, "{}", self.name.color(self.file_type().color())),
         }
     }
 }
@@ -109,29 +240,157 @@ impl Entry {
         self.metadata.permissions().mode() & 0o111 != 0
     }
 
+    fn file_type(&self) -> FileType {
+        FileType::classify(&self.kind, &self.name)
+    }
+
+    /// The leading icon glyph plus a trailing space when `--icons` is set,
+    /// or an empty string otherwise. Counted separately from `name.len()` by
+    /// callers doing column-width math.
+    fn icon_prefix(&self) -> String {
+        if self.icons {
+            format!(
+                "{} ",
+                self.file_type().icon().color(self.file_type().color())
+            )
+        } else {
+            String::new()
+        }
+    }
+
     fn len(&self) -> usize {
+        let icon_width = if self.icons { 2 } else { 0 };
+
         if self.kind == Kind::Directory {
-            self.name.len() + 1
+            self.name.len() + 1 + icon_width
         } else {
-            self.name.len()
+            self.name.len() + icon_width
         }
     }
 }
 
+/// Renders `mode` as a 10-character `ls -l` style permission string, folding
+/// the setuid/setgid/sticky bits into the owner/group/other execute slots.
+fn format_permissions(kind: &Kind, mode: u32) -> String {
+    let mut permissions = String::with_capacity(10);
+
+    permissions.push(match kind {
+        Kind::Directory => 'd',
+        Kind::Symlink => 'l',
+        Kind::File => '-',
+    });
+
+    let setuid = mode & 0o4000 != 0;
+    let setgid = mode & 0o2000 != 0;
+    let sticky = mode & 0o1000 != 0;
+
+    permissions.push(if mode & 0o400 != 0 { 'r' } else { '-' });
+    permissions.push(if mode & 0o200 != 0 { 'w' } else { '-' });
+    permissions.push(match (mode & 0o100 != 0, setuid) {
+        (true, true) => 's',
+        (false, true) => 'S',
+        (true, false) => 'x',
+        (false, false) => '-',
+    });
+
+    permissions.push(if mode & 0o040 != 0 { 'r' } else { '-' });
+    permissions.push(if mode & 0o020 != 0 { 'w' } else { '-' });
+    permissions.push(match (mode & 0o010 != 0, setgid) {
+        (true, true) => 's',
+        (false, true) => 'S',
+        (true, false) => 'x',
+        (false, false) => '-',
+    });
+
+    permissions.push(if mode & 0o004 != 0 { 'r' } else { '-' });
+    permissions.push(if mode & 0o002 != 0 { 'w' } else { '-' });
+    permissions.push(match (mode & 0o001 != 0, sticky) {
+        (true, true) => 't',
+        (false, true) => 'T',
+        (true, false) => 'x',
+        (false, false) => '-',
+    });
+
+    permissions
+}
+
+/// Colorizes a permission string the way eza does: the type char by kind,
+/// `r`/`w`/`x` each in their own color, and dashes dimmed.
+fn colorize_permissions(permissions: &str) -> String {
+    permissions
+        .chars()
+        .enumerate()
+        .map(|(index, ch)| match (index, ch) {
+            (0, 'd') => ch.to_string().blue().bold().to_string(),
+            (0, 'l') => ch.to_string().bright_blue().to_string(),
+            (_, 'r') => ch.to_string().yellow().to_string(),
+            (_, 'w') => ch.to_string().red().to_string(),
+            (_, 'x') => ch.to_string().green().to_string(),
+            (_, 's' | 'S' | 't' | 'T') => ch.to_string().magenta().to_string(),
+            _ => ch.to_string().dimmed().to_string(),
+        })
+        .collect()
+}
+
+/// Formats `mtime` (seconds since the epoch) the way `ls -l` does: `Mon DD
+/// HH:MM` for recent timestamps, falling back to `Mon DD  YYYY` once the
+/// file is older than about six months.
+fn format_mtime(mtime: i64) -> String {
+    let modified = DateTime::from_timestamp(mtime, 0)
+        .unwrap_or_default()
+        .with_timezone(&Local);
+
+    let six_months_ago: DateTime<Local> = (SystemTime::now() - SIX_MONTHS).into();
+
+    if modified < six_months_ago {
+        modified.format("%b %e  %Y").to_string()
+    } else {
+        modified.format("%b %e %H:%M").to_string()
+    }
+}
+
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug)]
 pub struct LongEntry {
     entry: Entry,
+    permissions: String,
+    links: String,
     owner: String,
     group: String,
+    size_bytes: u64,
     size: String,
+    modified: String,
     link_target: Option<String>,
+    git_status: GitCell,
+    xattrs: Vec<(String, usize)>,
+}
+
+/// Lists the extended attributes on `path` as `(name, byte length)` pairs.
+/// Returns an empty `Vec` with no overhead when there are none, and quietly
+/// ignores a single unreadable attribute (e.g. `ENOTSUP`) instead of failing
+/// the whole listing.
+fn list_xattrs(path: &path::Path) -> Vec<(String, usize)> {
+    let Ok(names) = xattr::list(path) else {
+        return Vec::new();
+    };
+
+    names
+        .filter_map(|name| {
+            let length = xattr::get(path, &name).ok().flatten()?.len();
+            Some((name.to_string_lossy().into_owned(), length))
+        })
+        .collect()
 }
 
 impl TryFrom<Entry> for LongEntry {
     type Error = Error;
 
     fn try_from(entry: Entry) -> Result<Self> {
+        let permissions = format_permissions(&entry.kind, entry.metadata.mode());
+        let links = entry.metadata.nlink().to_string();
+        let modified = format_mtime(entry.metadata.mtime());
+        let xattrs = list_xattrs(&entry.dir_entry.path());
+
         let owner = unsafe {
             let passwd = libc::getpwuid(entry.metadata.uid());
             ffi::CStr::from_ptr((*passwd).pw_name).to_string_lossy()
@@ -144,12 +403,8 @@ impl TryFrom<Entry> for LongEntry {
         }
         .to_string();
 
-        let size = match entry.metadata.size() {
-            b if b < KILOBYTE => format!("{b}B"),
-            b if b < GIGABYTE => format!("{}.{}K", b / KILOBYTE, (b % KILOBYTE) / (KILOBYTE / 10)),
-            b if b < TERABYTE => format!("{}.{}G", b / GIGABYTE, (b % GIGABYTE) / (GIGABYTE / 10)),
-            b => format!("{}.{}T", b / TERABYTE, (b % TERABYTE) / (TERABYTE / 10)),
-        };
+        let size_bytes = entry.metadata.size();
+        let size = format_size(size_bytes, SizeUnit::default());
 
         let link_target = if entry.kind == Kind::Symlink {
             let target = fs::read_link(entry.dir_entry.path())?;
@@ -160,20 +415,41 @@ impl TryFrom<Entry> for LongEntry {
 
         Ok(Self {
             entry,
+            permissions,
+            links,
             owner,
             group,
+            size_bytes,
             size,
+            modified,
             link_target,
+            git_status: GitCell::default(),
+            xattrs,
         })
     }
 }
 
+impl LongEntry {
+    pub fn path(&self) -> path::PathBuf {
+        self.entry.dir_entry.path()
+    }
+
+    pub fn set_size_unit(&mut self, size_unit: SizeUnit) {
+        self.size = format_size(self.size_bytes, size_unit);
+    }
+
+    pub fn set_git_status(&mut self, git_status: GitCell) {
+        self.git_status = git_status;
+    }
+}
+
 impl fmt::Display for LongEntry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.entry.kind == Kind::Symlink {
             return write!(
                 f,
-                "{} -> {}",
+                "{}{} -> {}",
+                self.entry.icon_prefix(),
                 self.entry.name.bright_blue().underline(),
                 self.link_target.clone().unwrap()
             );
@@ -183,22 +459,80 @@ impl fmt::Display for LongEntry {
     }
 }
 
-pub fn read_entries(path: path::PathBuf, all: bool) -> Result<Vec<Entry>> {
+pub fn read_entries(path: path::PathBuf, all: bool, icons: bool) -> Result<Vec<Entry>> {
     let dir_entries = fs::read_dir(path)?.collect::<io::Result<Vec<_>>>()?;
     let mut entries = Vec::new();
 
     for dir_entry in dir_entries {
-        let entry: Entry = dir_entry.try_into()?;
+        let mut entry: Entry = dir_entry.try_into()?;
         if !all && entry.is_hidden() {
             continue;
         }
 
+        entry.icons = icons;
         entries.push(entry);
     }
 
     Ok(entries)
 }
 
+/// An [`Entry`] together with its (already sorted) children, used by
+/// [`print_entries_tree`]. Only populated for directories; symlinks are
+/// never recursed into, even when they point at a directory, to avoid
+/// cycles.
+pub struct TreeNode {
+    entry: Entry,
+    children: Vec<TreeNode>,
+}
+
+pub fn read_entries_tree(
+    path: path::PathBuf,
+    all: bool,
+    icons: bool,
+    max_depth: Option<usize>,
+) -> Result<Vec<TreeNode>> {
+    let mut entries = read_entries(path, all, icons)?;
+    entries.sort();
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let recurse = entry.kind == Kind::Directory && max_depth != Some(0);
+
+            let children = if recurse {
+                read_entries_tree(
+                    entry.dir_entry.path(),
+                    all,
+                    icons,
+                    max_depth.map(|depth| depth - 1),
+                )?
+            } else {
+                Vec::new()
+            };
+
+            Ok(TreeNode { entry, children })
+        })
+        .collect()
+}
+
+pub fn print_entries_tree(nodes: &[TreeNode]) {
+    print_tree_level(nodes, "");
+}
+
+fn print_tree_level(nodes: &[TreeNode], prefix: &str) {
+    for (index, node) in nodes.iter().enumerate() {
+        let is_last = index == nodes.len() - 1;
+        let branch = if is_last { "└── " } else { "├── " };
+
+        println!("{prefix}{branch}{}", node.entry);
+
+        if !node.children.is_empty() {
+            let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+            print_tree_level(&node.children, &child_prefix);
+        }
+    }
+}
+
 pub fn print_entries_short(entries: Vec<Entry>) {
     let Width(width) = terminal_size().map_or(Width(80), |size| size.0);
 
@@ -232,21 +566,42 @@ pub fn print_entries_short(entries: Vec<Entry>) {
     }
 }
 
-pub fn print_entries_long(entries: Vec<LongEntry>) {
+pub fn print_entries_long(entries: Vec<LongEntry>, show_git: bool, show_xattrs: bool) {
+    let max_links_width = max_field_width!(entries, links);
     let max_owner_width = max_field_width!(entries, owner);
     let max_group_width = max_field_width!(entries, group);
     let max_size_width = max_field_width!(entries, size);
 
     for entry in entries {
+        let git_status = if show_git {
+            format!("{} ", entry.git_status.render())
+        } else {
+            String::new()
+        };
+
+        let xattr_marker = if entry.xattrs.is_empty() { " " } else { "@" };
+
         println!(
-            "{}{} {}{} {}{} {}",
+            "{}{}{} {}{} {}{} {}{} {}{} {} {}",
+            git_status,
+            colorize_permissions(&entry.permissions),
+            xattr_marker.dimmed(),
+            " ".repeat(max_links_width - entry.links.len()),
+            entry.links.white(),
             " ".repeat(max_owner_width - entry.owner.len()),
             entry.owner,
             " ".repeat(max_group_width - entry.group.len()),
             entry.group,
             " ".repeat(max_size_width - entry.size.len()),
             entry.size,
+            entry.modified.bright_black(),
             entry
         );
+
+        if show_xattrs {
+            for (name, length) in &entry.xattrs {
+                println!("    {} ({length} bytes)", name.dimmed());
+            }
+        }
     }
 }
@@ -0,0 +1,211 @@
+use colored::Color;
+
+use crate::entry::Kind;
+
+/// Broad buckets an entry's name/extension can fall into, each with its own
+/// color and Nerd Font glyph. Kept flat and easy to extend with new
+/// extensions rather than modeled as a deep type hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    Directory,
+    Symlink,
+    Source,
+    Image,
+    Archive,
+    Video,
+    Audio,
+    Document,
+    Temporary,
+    Lock,
+    Other,
+}
+
+const EXTENSION_CATEGORIES: &[(&str, Category)] = &[
+    ("rs", Category::Source),
+    ("toml", Category::Source),
+    ("py", Category::Source),
+    ("js", Category::Source),
+    ("ts", Category::Source),
+    ("jsx", Category::Source),
+    ("tsx", Category::Source),
+    ("go", Category::Source),
+    ("c", Category::Source),
+    ("h", Category::Source),
+    ("cpp", Category::Source),
+    ("hpp", Category::Source),
+    ("java", Category::Source),
+    ("rb", Category::Source),
+    ("sh", Category::Source),
+    ("md", Category::Document),
+    ("txt", Category::Document),
+    ("pdf", Category::Document),
+    ("doc", Category::Document),
+    ("docx", Category::Document),
+    ("png", Category::Image),
+    ("jpg", Category::Image),
+    ("jpeg", Category::Image),
+    ("gif", Category::Image),
+    ("svg", Category::Image),
+    ("bmp", Category::Image),
+    ("webp", Category::Image),
+    ("mp4", Category::Video),
+    ("mkv", Category::Video),
+    ("mov", Category::Video),
+    ("avi", Category::Video),
+    ("webm", Category::Video),
+    ("mp3", Category::Audio),
+    ("wav", Category::Audio),
+    ("flac", Category::Audio),
+    ("ogg", Category::Audio),
+    ("zip", Category::Archive),
+    ("tar", Category::Archive),
+    ("gz", Category::Archive),
+    ("bz2", Category::Archive),
+    ("xz", Category::Archive),
+    ("7z", Category::Archive),
+    ("rar", Category::Archive),
+    ("bak", Category::Temporary),
+    ("tmp", Category::Temporary),
+    ("swp", Category::Temporary),
+    ("orig", Category::Temporary),
+];
+
+const NAME_CATEGORIES: &[(&str, Category)] = &[
+    ("Cargo.lock", Category::Lock),
+    ("package-lock.json", Category::Lock),
+    ("yarn.lock", Category::Lock),
+    ("pnpm-lock.yaml", Category::Lock),
+    ("Gemfile.lock", Category::Lock),
+];
+
+/// Per-extension icon overrides for `Category::Source`, so e.g. `.rs` and
+/// `.py` get distinct glyphs instead of sharing one generic "source" icon.
+/// Extensions not listed here fall back to the category's icon.
+const EXTENSION_ICONS: &[(&str, &str)] = &[
+    ("rs", "\u{e7a8}"),   //
+    ("py", "\u{e73c}"),   //
+    ("go", "\u{e626}"),   //
+    ("js", "\u{e74e}"),   //
+    ("jsx", "\u{e7ba}"),  //
+    ("ts", "\u{e628}"),   //
+    ("tsx", "\u{e7ba}"),  //
+    ("java", "\u{e738}"), //
+    ("rb", "\u{e21e}"),   //
+    ("sh", "\u{f489}"),   //
+    ("c", "\u{e61e}"),    //
+    ("h", "\u{f0fd}"),
+    ("cpp", "\u{e61d}"), //
+    ("hpp", "\u{f0fd}"),
+    ("toml", "\u{e6b2}"), //
+];
+
+struct Style {
+    color: Color,
+    icon: &'static str,
+}
+
+fn style(category: Category) -> Style {
+    match category {
+        Category::Directory => Style {
+            color: Color::Blue,
+            icon: "\u{f115}", //
+        },
+        Category::Symlink => Style {
+            color: Color::BrightBlue,
+            icon: "\u{f481}", //
+        },
+        Category::Source => Style {
+            color: Color::Yellow,
+            icon: "\u{e7a8}", //
+        },
+        Category::Image => Style {
+            color: Color::Magenta,
+            icon: "\u{f1c5}", //
+        },
+        Category::Archive => Style {
+            color: Color::Red,
+            icon: "\u{f410}", //
+        },
+        Category::Video => Style {
+            color: Color::BrightMagenta,
+            icon: "\u{f03d}", //
+        },
+        Category::Audio => Style {
+            color: Color::Cyan,
+            icon: "\u{f001}", //
+        },
+        Category::Document => Style {
+            color: Color::White,
+            icon: "\u{f15c}", //
+        },
+        Category::Temporary => Style {
+            color: Color::BrightBlack,
+            icon: "\u{f1f8}", //
+        },
+        Category::Lock => Style {
+            color: Color::BrightYellow,
+            icon: "\u{f023}", //
+        },
+        Category::Other => Style {
+            color: Color::White,
+            icon: "\u{f15b}", //
+        },
+    }
+}
+
+fn extension_of(name: &str) -> Option<String> {
+    name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase())
+}
+
+fn extension_category(name: &str, extension: Option<&str>) -> Option<Category> {
+    if let Some((_, category)) = NAME_CATEGORIES.iter().find(|(n, _)| *n == name) {
+        return Some(*category);
+    }
+
+    let extension = extension?;
+    EXTENSION_CATEGORIES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, category)| *category)
+}
+
+/// Classifies an entry's extension/name into a [`Category`] and exposes the
+/// color and icon that category renders with in both short and long mode.
+#[derive(Debug, Clone, Copy)]
+pub struct FileType {
+    category: Category,
+    extension_icon: Option<&'static str>,
+}
+
+impl FileType {
+    pub fn classify(kind: &Kind, name: &str) -> Self {
+        let extension = extension_of(name);
+
+        let category = match kind {
+            Kind::Directory => Category::Directory,
+            Kind::Symlink => Category::Symlink,
+            Kind::File => extension_category(name, extension.as_deref()).unwrap_or(Category::Other),
+        };
+
+        let extension_icon = extension.filter(|_| category == Category::Source).and_then(|extension| {
+            EXTENSION_ICONS
+                .iter()
+                .find(|(ext, _)| *ext == extension)
+                .map(|(_, icon)| *icon)
+        });
+
+        Self {
+            category,
+            extension_icon,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        style(self.category).color
+    }
+
+    pub fn icon(self) -> &'static str {
+        self.extension_icon
+            .unwrap_or_else(|| style(self.category).icon)
+    }
+}
@@ -0,0 +1,154 @@
+use colored::Colorize;
+use git2::{Repository, Status, StatusOptions};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+/// A two-character `git status`-style cell: the index (staged) state and the
+/// working-tree (unstaged) state, each one of `N`/`M`/`D`/`R`/`I`/`-`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GitCell {
+    index: char,
+    worktree: char,
+}
+
+impl Default for GitCell {
+    fn default() -> Self {
+        Self {
+            index: '-',
+            worktree: '-',
+        }
+    }
+}
+
+impl GitCell {
+    /// Ranks cells so a directory can show the "worst" status among its
+    /// children: deleted/modified outrank new/renamed, which outrank ignored.
+    fn rank(ch: char) -> u8 {
+        match ch {
+            'D' => 5,
+            'M' => 4,
+            'N' => 3,
+            'R' => 2,
+            'I' => 1,
+            _ => 0,
+        }
+    }
+
+    fn worse_char(a: char, b: char) -> char {
+        if Self::rank(b) > Self::rank(a) {
+            b
+        } else {
+            a
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            index: Self::worse_char(self.index, other.index),
+            worktree: Self::worse_char(self.worktree, other.worktree),
+        }
+    }
+
+    fn colorize(ch: char) -> String {
+        match ch {
+            'N' => ch.to_string().green().to_string(),
+            'M' => ch.to_string().yellow().to_string(),
+            'D' => ch.to_string().red().to_string(),
+            'R' => ch.to_string().blue().to_string(),
+            'I' => ch.to_string().dimmed().to_string(),
+            _ => ch.to_string().dimmed().to_string(),
+        }
+    }
+
+    pub fn render(self) -> String {
+        format!(
+            "{}{}",
+            Self::colorize(self.index),
+            Self::colorize(self.worktree)
+        )
+    }
+}
+
+fn index_char(status: Status) -> char {
+    if status.is_index_new() {
+        'N'
+    } else if status.is_index_deleted() {
+        'D'
+    } else if status.is_index_renamed() {
+        'R'
+    } else if status.is_index_modified() || status.is_index_typechange() {
+        'M'
+    } else {
+        '-'
+    }
+}
+
+fn worktree_char(status: Status) -> char {
+    if status.is_wt_new() {
+        'N'
+    } else if status.is_wt_deleted() {
+        'D'
+    } else if status.is_wt_renamed() {
+        'R'
+    } else if status.is_wt_modified() || status.is_wt_typechange() {
+        'M'
+    } else if status.is_ignored() {
+        'I'
+    } else {
+        '-'
+    }
+}
+
+pub type StatusMap = HashMap<PathBuf, GitCell>;
+
+/// Discovers the repository containing `path` and builds a map from absolute
+/// path to [`GitCell`] in one pass, so callers can look an entry's status up
+/// in O(1) instead of re-walking the repository per entry. Directories get
+/// the "worst" status among their descendants, untracked and ignored ones
+/// included. Returns `None` when
+/// `path` is not inside a Git repository.
+pub fn build_status_map(path: &Path) -> Option<StatusMap> {
+    let repo = Repository::discover(path).ok()?;
+    let workdir = repo.workdir()?.to_path_buf();
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+    let mut map = StatusMap::new();
+
+    for status_entry in statuses.iter() {
+        let Some(relative) = status_entry.path() else {
+            continue;
+        };
+
+        let cell = GitCell {
+            index: index_char(status_entry.status()),
+            worktree: worktree_char(status_entry.status()),
+        };
+
+        let absolute = workdir.join(relative);
+        map.insert(absolute.clone(), cell);
+
+        let mut ancestor = absolute.parent();
+        while let Some(dir) = ancestor {
+            map.entry(dir.to_path_buf())
+                .and_modify(|existing| *existing = existing.merge(cell))
+                .or_insert(cell);
+
+            if dir == workdir {
+                break;
+            }
+            ancestor = dir.parent();
+        }
+    }
+
+    Some(map)
+}
@@ -1,11 +1,49 @@
 mod entry;
 mod error;
+mod file_type;
+mod git;
 
 use crate::error::{Error, Result};
 use std::{env, path};
 
+/// Parsed command-line options. Grew out of the original `(all, long)`
+/// tuple once enough independent toggles piled up that positional booleans
+/// stopped being readable.
+#[derive(Debug)]
+struct Flags {
+    all: bool,
+    long: bool,
+    git: bool,
+    icons: bool,
+    tree: bool,
+    level: Option<usize>,
+    sort_field: entry::SortField,
+    reverse: bool,
+    dirs_first: bool,
+    extended: bool,
+    size_unit: entry::SizeUnit,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self {
+            all: false,
+            long: false,
+            git: false,
+            icons: false,
+            tree: false,
+            level: None,
+            sort_field: entry::SortField::default(),
+            reverse: false,
+            dirs_first: true,
+            extended: false,
+            size_unit: entry::SizeUnit::default(),
+        }
+    }
+}
+
 fn main() {
-    let (all, long) = parse_flags();
+    let flags = parse_flags();
 
     let cwd = env::current_dir()
         .map_err(Error::from)
@@ -13,46 +51,150 @@ fn main() {
 
     let path = parse_args().map(|path| cwd.join(path)).unwrap_or(cwd);
 
-    let mut entries = entry::read_entries(path, all).unwrap_or_else(|err| err.print_and_exit());
-    entries.sort();
+    if flags.tree {
+        let nodes = entry::read_entries_tree(path, flags.all, flags.icons, flags.level)
+            .unwrap_or_else(|err| err.print_and_exit());
+        entry::print_entries_tree(&nodes);
+        return;
+    }
+
+    let mut entries = entry::read_entries(path.clone(), flags.all, flags.icons)
+        .unwrap_or_else(|err| err.print_and_exit());
+    entries.sort_by(entry::comparator(
+        flags.sort_field,
+        flags.reverse,
+        flags.dirs_first,
+    ));
 
-    if long {
-        let long_entries: Vec<entry::LongEntry> = entries
+    if flags.long {
+        let mut long_entries: Vec<entry::LongEntry> = entries
             .into_iter()
             .map(entry::Entry::try_into)
             .collect::<Result<Vec<_>>>()
             .unwrap_or_else(|err| err.print_and_exit());
-        entry::print_entries_long(long_entries);
+
+        for long_entry in &mut long_entries {
+            long_entry.set_size_unit(flags.size_unit);
+        }
+
+        let show_git = flags.git
+            && git::build_status_map(&path)
+                .map(|status_map| {
+                    for long_entry in &mut long_entries {
+                        long_entry.set_git_status(
+                            status_map
+                                .get(&long_entry.path())
+                                .copied()
+                                .unwrap_or_default(),
+                        );
+                    }
+                })
+                .is_some();
+
+        entry::print_entries_long(long_entries, show_git, flags.extended);
     } else {
         entry::print_entries_short(entries);
     }
 }
 
-fn parse_flags() -> (bool, bool) {
-    let mut all = false;
-    let mut long = false;
+fn parse_sort_field(value: &str) -> entry::SortField {
+    match value {
+        "name" => entry::SortField::Name,
+        "size" => entry::SortField::Size,
+        "time" => entry::SortField::Time,
+        "extension" => entry::SortField::Extension,
+        "kind" => entry::SortField::Kind,
+        _ => Error::UnknownFlag(format!("--sort={value}")).print_and_exit(),
+    }
+}
+
+fn parse_flags() -> Flags {
+    let mut flags = Flags::default();
 
     let args = env::args()
         .skip(1)
         .take_while(|arg| arg.starts_with('-') && arg != "--");
 
     for arg in args {
+        if arg == "--git" {
+            flags.git = true;
+            continue;
+        }
+
+        if arg == "--icons" {
+            flags.icons = true;
+            continue;
+        }
+
+        if arg == "--tree" {
+            flags.tree = true;
+            continue;
+        }
+
+        if arg == "--no-dirs-first" {
+            flags.dirs_first = false;
+            continue;
+        }
+
+        if arg == "--extended" {
+            flags.extended = true;
+            continue;
+        }
+
+        if arg == "--bytes" {
+            flags.size_unit = entry::SizeUnit::Bytes;
+            continue;
+        }
+
+        if arg == "--binary" {
+            flags.size_unit = entry::SizeUnit::Binary;
+            continue;
+        }
+
+        if let Some(value) = arg.strip_prefix("--level=") {
+            flags.level = Some(
+                value
+                    .parse()
+                    .unwrap_or_else(|_| Error::UnknownFlag(arg.clone()).print_and_exit()),
+            );
+            continue;
+        }
+
+        if let Some(value) = arg.strip_prefix("--sort=") {
+            flags.sort_field = parse_sort_field(value);
+            continue;
+        }
+
         for flag in arg
             .trim_start_matches('-')
             .split("")
             .filter(|flag| !flag.is_empty())
         {
             if flag == "a" {
-                all = true;
+                flags.all = true;
             } else if flag == "l" {
-                long = true;
+                flags.long = true;
+            } else if flag == "g" {
+                flags.git = true;
+            } else if flag == "T" {
+                flags.tree = true;
+            } else if flag == "t" {
+                flags.sort_field = entry::SortField::Time;
+            } else if flag == "S" {
+                flags.sort_field = entry::SortField::Size;
+            } else if flag == "r" {
+                flags.reverse = true;
+            } else if flag == "@" {
+                flags.extended = true;
+            } else if flag == "b" {
+                flags.size_unit = entry::SizeUnit::Binary;
             } else {
                 Error::UnknownFlag(flag.into()).print_and_exit();
             }
         }
     }
 
-    (all, long)
+    flags
 }
 
 fn parse_args() -> Option<path::PathBuf> {